@@ -0,0 +1,7 @@
+pub mod configuration;
+pub mod domain;
+pub mod email_client;
+pub mod migrations;
+pub mod routes;
+pub mod startup;
+pub mod telemetry;
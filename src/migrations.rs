@@ -0,0 +1,131 @@
+use sha2::{Digest, Sha256};
+use sqlx::{Executor, PgPool, Row};
+use std::path::Path;
+
+#[derive(thiserror::Error, Debug)]
+pub enum MigrationError {
+    #[error("migration file name `{0}` does not start with a numeric version prefix followed by `_`")]
+    InvalidFileName(String),
+    #[error(
+        "checksum mismatch for migration {version} ({name}): the applied migration has been \
+         modified on disk since it ran"
+    )]
+    ChecksumMismatch { version: i64, name: String },
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
+
+struct MigrationFile {
+    version: i64,
+    name: String,
+    checksum: String,
+    sql: String,
+}
+
+fn parse_migration_file(dir: &Path, file_name: &str) -> Result<MigrationFile, MigrationError> {
+    let (version_part, name) = file_name
+        .split_once('_')
+        .ok_or_else(|| MigrationError::InvalidFileName(file_name.to_string()))?;
+    let version: i64 = version_part
+        .parse()
+        .map_err(|_| MigrationError::InvalidFileName(file_name.to_string()))?;
+    let name = name.trim_end_matches(".sql").to_string();
+    let sql = std::fs::read_to_string(dir.join(file_name))?;
+    let checksum = format!("{:x}", Sha256::digest(sql.as_bytes()));
+
+    Ok(MigrationFile {
+        version,
+        name,
+        checksum,
+        sql,
+    })
+}
+
+fn read_migration_files(migration_dir: &Path) -> Result<Vec<MigrationFile>, MigrationError> {
+    let mut file_names: Vec<String> = std::fs::read_dir(migration_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .map(|ext| ext == "sql")
+                .unwrap_or(false)
+        })
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    file_names.sort();
+
+    file_names
+        .into_iter()
+        .map(|file_name| parse_migration_file(migration_dir, &file_name))
+        .collect()
+}
+
+async fn ensure_migrations_table(pool: &PgPool) -> Result<(), sqlx::Error> {
+    pool.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS _nts_migrations (
+            version BIGINT PRIMARY KEY,
+            name TEXT NOT NULL,
+            checksum TEXT NOT NULL,
+            applied_at timestamptz NOT NULL DEFAULT now()
+        )
+        "#,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Applies every pending migration found in `migration_dir` to `pool`, in order, one transaction
+/// per file. Returns the versions that were newly applied.
+pub async fn apply_pending(pool: &PgPool, migration_dir: &Path) -> Result<Vec<i64>, MigrationError> {
+    ensure_migrations_table(pool).await?;
+
+    let files = read_migration_files(migration_dir)?;
+    let mut applied = Vec::new();
+
+    for file in files {
+        let existing: Option<(String,)> =
+            sqlx::query_as("SELECT checksum FROM _nts_migrations WHERE version = $1")
+                .bind(file.version)
+                .fetch_optional(pool)
+                .await?;
+
+        match existing {
+            Some((checksum,)) if checksum == file.checksum => continue,
+            Some(_) => {
+                return Err(MigrationError::ChecksumMismatch {
+                    version: file.version,
+                    name: file.name,
+                })
+            }
+            None => {}
+        }
+
+        let mut transaction = pool.begin().await?;
+        transaction.execute(file.sql.as_str()).await?;
+        sqlx::query(
+            "INSERT INTO _nts_migrations (version, name, checksum) VALUES ($1, $2, $3)",
+        )
+        .bind(file.version)
+        .bind(&file.name)
+        .bind(&file.checksum)
+        .execute(&mut *transaction)
+        .await?;
+        transaction.commit().await?;
+
+        applied.push(file.version);
+    }
+
+    Ok(applied)
+}
+
+/// Returns the versions of every migration recorded in `_nts_migrations`, in order.
+pub async fn applied_versions(pool: &PgPool) -> Result<Vec<i64>, sqlx::Error> {
+    let rows = sqlx::query("SELECT version FROM _nts_migrations ORDER BY version")
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(|row| row.get("version")).collect())
+}
@@ -0,0 +1,82 @@
+use crate::domain::SubscriberEmail;
+use crate::email_client::EmailClient;
+use actix_web::{web, HttpResponse};
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[derive(serde::Deserialize)]
+pub struct FormData {
+    email: String,
+    name: String,
+}
+
+#[tracing::instrument(
+    name = "Adding a new subscriber",
+    skip(form, pool, email_client),
+    fields(subscriber_email = %form.email, subscriber_name = %form.name)
+)]
+pub async fn subscribe(
+    form: web::Form<FormData>,
+    pool: web::Data<PgPool>,
+    email_client: web::Data<EmailClient>,
+) -> HttpResponse {
+    let email = match SubscriberEmail::parse(form.0.email.clone()) {
+        Ok(email) => email,
+        Err(_) => return HttpResponse::BadRequest().finish(),
+    };
+
+    let subscriber_id = Uuid::new_v4();
+    if insert_subscriber(&pool, subscriber_id, &email, &form.0.name)
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().finish();
+    }
+
+    if send_confirmation_email(&email_client, &email)
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().finish();
+    }
+
+    HttpResponse::Ok().finish()
+}
+
+#[tracing::instrument(name = "Saving new subscriber details in the database", skip(pool))]
+async fn insert_subscriber(
+    pool: &PgPool,
+    subscriber_id: Uuid,
+    email: &SubscriberEmail,
+    name: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO subscriptions (id, email, name, subscribed_at, status)
+        VALUES ($1, $2, $3, $4, 'pending_confirmation')
+        "#,
+    )
+    .bind(subscriber_id)
+    .bind(email.as_ref())
+    .bind(name)
+    .bind(Utc::now())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[tracing::instrument(name = "Send a confirmation email to a new subscriber", skip(email_client))]
+async fn send_confirmation_email(
+    email_client: &EmailClient,
+    email: &SubscriberEmail,
+) -> Result<(), reqwest::Error> {
+    email_client
+        .send_email(
+            email,
+            "Welcome!",
+            "Welcome to our newsletter!",
+            "Welcome to our newsletter!",
+        )
+        .await
+}
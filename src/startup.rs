@@ -0,0 +1,87 @@
+use crate::configuration::{get_connection_pool, Settings};
+use crate::email_client::EmailClient;
+use crate::migrations;
+use crate::routes::{health_check, subscribe};
+use actix_web::dev::Server;
+use actix_web::web::Data;
+use actix_web::{web, App, HttpServer};
+use sqlx::PgPool;
+use std::net::TcpListener;
+
+pub struct Application {
+    port: u16,
+    server: Server,
+    db_pool: PgPool,
+}
+
+impl Application {
+    pub async fn build(configuration: Settings) -> Result<Self, std::io::Error> {
+        let connection_pool = get_connection_pool(&configuration.database);
+
+        let sender_email = configuration
+            .email_client
+            .sender()
+            .expect("Invalid sender email address.");
+        let timeout = configuration.email_client.timeout();
+        let email_client = EmailClient::new(
+            configuration.email_client.base_url,
+            sender_email,
+            configuration.email_client.authorization_token,
+            timeout,
+        );
+
+        let address = format!(
+            "{}:{}",
+            configuration.application.host, configuration.application.port
+        );
+        let listener = TcpListener::bind(address)?;
+        let port = listener.local_addr()?.port();
+        let db_pool = connection_pool.clone();
+        let server = run(listener, connection_pool, email_client).await?;
+
+        Ok(Self {
+            port,
+            server,
+            db_pool,
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns a clone of the pool backing the running server, so callers that need to
+    /// tear down shared state (e.g. dropping the test database) can close the very
+    /// connections the server holds open.
+    pub fn db_pool(&self) -> PgPool {
+        self.db_pool.clone()
+    }
+
+    pub async fn run_until_stopped(self) -> Result<(), std::io::Error> {
+        self.server.await
+    }
+}
+
+pub async fn run(
+    listener: TcpListener,
+    db_pool: PgPool,
+    email_client: EmailClient,
+) -> Result<Server, std::io::Error> {
+    let migration_dir = std::env::current_dir()?.join("migrations");
+    migrations::apply_pending(&db_pool, &migration_dir)
+        .await
+        .map_err(std::io::Error::other)?;
+
+    let db_pool = Data::new(db_pool);
+    let email_client = Data::new(email_client);
+    let server = HttpServer::new(move || {
+        App::new()
+            .route("/health_check", web::get().to(health_check))
+            .route("/subscriptions", web::post().to(subscribe))
+            .app_data(db_pool.clone())
+            .app_data(email_client.clone())
+    })
+    .listen(listener)?
+    .run();
+    Ok(server)
+}
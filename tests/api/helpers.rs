@@ -1,11 +1,10 @@
 use nts::configuration::{get_configuration, DatabaseSettings};
-use nts::email_client::EmailClient;
-use nts::startup::run;
+use nts::startup::Application;
 use nts::telemetry::{get_subscriber, init_subscriber};
 use once_cell::sync::Lazy;
 use sqlx::{Connection, Executor, PgConnection, PgPool};
-use std::net::TcpListener;
 use uuid::Uuid;
+use wiremock::MockServer;
 
 static TRACING: Lazy<()> = Lazy::new(|| {
     let default_filter_level = "info".to_string();
@@ -22,10 +21,34 @@ static TRACING: Lazy<()> = Lazy::new(|| {
 pub struct TestApp {
     pub address: String,
     pub db_pool: PgPool,
+    pub email_server: MockServer,
+    api_client: reqwest::Client,
     database_settings: DatabaseSettings,
 }
 
 impl TestApp {
+    pub async fn get(&self, path: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}{}", self.address, path))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_health_check(&self) -> reqwest::Response {
+        self.get("/health_check").await
+    }
+
+    pub async fn post_subscriptions(&self, body: String) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/subscriptions", self.address))
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
     pub async fn teardown_database(&self) {
         self.db_pool.close().await;
 
@@ -57,38 +80,36 @@ impl TestApp {
 pub async fn spawn_app() -> TestApp {
     Lazy::force(&TRACING);
 
-    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind random port");
-    let port = listener.local_addr().unwrap().port();
-    let address = format!("http://127.0.0.1:{}", port);
+    let email_server = MockServer::start().await;
 
-    let mut configuration = get_configuration().expect("Failed to read configuration.");
-    configuration.database.database_name = Uuid::new_v4().to_string();
-    let connection_pool = configure_database(&configuration.database).await;
+    let configuration = {
+        let mut c = get_configuration().expect("Failed to read configuration.");
+        c.database.database_name = Uuid::new_v4().to_string();
+        c.application.port = 0;
+        c.email_client.base_url = email_server.uri();
+        c
+    };
 
-    let sender_email = configuration
-        .email_client
-        .sender()
-        .expect("Invalid sender email address.");
-    let timeout = configuration.email_client.timeout();
-    let email_client = EmailClient::new(
-        configuration.email_client.base_url,
-        sender_email,
-        configuration.email_client.authorization_token,
-        timeout,
-    );
+    configure_database(&configuration.database).await;
 
-    let server =
-        run(listener, connection_pool.clone(), email_client).expect("Failed to bind address");
-    let _ = tokio::spawn(server);
+    let application = Application::build(configuration.clone())
+        .await
+        .expect("Failed to build application.");
+    let application_port = application.port();
+    let address = format!("http://127.0.0.1:{}", application_port);
+    let db_pool = application.db_pool();
+    drop(tokio::spawn(application.run_until_stopped()));
 
     TestApp {
         address,
-        db_pool: connection_pool,
+        db_pool,
+        email_server,
+        api_client: reqwest::Client::new(),
         database_settings: configuration.database,
     }
 }
 
-async fn configure_database(config: &DatabaseSettings) -> PgPool {
+async fn configure_database(config: &DatabaseSettings) {
     let mut connection = PgConnection::connect_with(&config.without_db())
         .await
         .expect("Failed to connect to Postgres");
@@ -96,36 +117,4 @@ async fn configure_database(config: &DatabaseSettings) -> PgPool {
         .execute(format!(r#"CREATE DATABASE "{}";"#, config.database_name).as_str())
         .await
         .expect("Failed to create database");
-
-    let connection_pool = PgPool::connect_with(config.with_db())
-        .await
-        .expect("Failed to connect to Postgres");
-
-    // iterate over migration dir and execute query files
-    let mut files: Vec<_> = Vec::new();
-    let migration_dir = std::env::current_dir().unwrap().join("migrations");
-
-    if let Ok(entries) = std::fs::read_dir(&migration_dir) {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                files.push(entry.file_name());
-            } else {
-                panic!("Failed to read directory entry");
-            }
-        }
-    } else {
-        panic!("Failed to read migration directory");
-    }
-
-    files.sort();
-
-    for file in files.into_iter() {
-        let query = std::fs::read_to_string(&migration_dir.join(file)).unwrap();
-        connection_pool
-            .execute(query.as_str())
-            .await
-            .expect("Failed to execute migration query");
-    }
-
-    connection_pool
 }
@@ -0,0 +1,55 @@
+use crate::helpers::spawn_app;
+use nts::migrations::{applied_versions, apply_pending, MigrationError};
+use std::fs;
+
+#[tokio::test]
+async fn apply_pending_returns_newly_applied_versions() {
+    let app = spawn_app().await;
+    let migration_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    fs::write(
+        migration_dir.path().join("0100_add_marker_table.sql"),
+        "CREATE TABLE marker (id INT);",
+    )
+    .expect("Failed to write migration file");
+
+    let applied = apply_pending(&app.db_pool, migration_dir.path())
+        .await
+        .expect("Failed to apply migrations");
+    assert_eq!(applied, vec![100]);
+
+    let applied_again = apply_pending(&app.db_pool, migration_dir.path())
+        .await
+        .expect("Failed to apply migrations");
+    assert!(applied_again.is_empty());
+
+    let versions = applied_versions(&app.db_pool)
+        .await
+        .expect("Failed to read applied versions");
+    assert!(versions.contains(&100));
+
+    app.teardown_database().await;
+}
+
+#[tokio::test]
+async fn apply_pending_rejects_a_drifted_checksum() {
+    let app = spawn_app().await;
+    let migration_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let file_path = migration_dir.path().join("0200_add_drift_table.sql");
+    fs::write(&file_path, "CREATE TABLE drift (id INT);").expect("Failed to write migration file");
+
+    apply_pending(&app.db_pool, migration_dir.path())
+        .await
+        .expect("Failed to apply migrations");
+
+    fs::write(&file_path, "CREATE TABLE drift (id INT, extra INT);")
+        .expect("Failed to rewrite migration file");
+
+    let result = apply_pending(&app.db_pool, migration_dir.path()).await;
+
+    assert!(matches!(
+        result,
+        Err(MigrationError::ChecksumMismatch { version: 200, .. })
+    ));
+
+    app.teardown_database().await;
+}